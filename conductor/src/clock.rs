@@ -0,0 +1,32 @@
+use crate::tempo::Tempo;
+
+/// A point in time that a command should be applied at, expressed either
+/// as an absolute sample count or as a beat of a running metronome.
+#[derive(Debug, Copy, Clone)]
+pub enum ClockTime {
+	/// An absolute number of samples from when the backend started.
+	Sample(u64),
+	/// A beat of the metronome, counted from when it was started.
+	Beat(f64),
+}
+
+impl ClockTime {
+	/// Resolves this clock time to an absolute sample index.
+	///
+	/// `metronome_start_sample` is the sample at which the metronome
+	/// began counting beats; it's ignored for [`ClockTime::Sample`].
+	pub(crate) fn in_samples(
+		&self,
+		sample_rate: u32,
+		tempo: Tempo,
+		metronome_start_sample: u64,
+	) -> u64 {
+		match *self {
+			Self::Sample(sample) => sample,
+			Self::Beat(beat) => {
+				let seconds = tempo.beats_to_seconds(beat);
+				metronome_start_sample + (seconds * sample_rate as f64).round() as u64
+			}
+		}
+	}
+}
@@ -1,6 +1,8 @@
 use crate::{
+	clock::ClockTime,
 	instance::{InstanceId, InstanceSettings},
 	manager::LoopSettings,
+	quantization::Quantization,
 	sequence::{Sequence, SequenceId},
 	sound::{Sound, SoundId},
 	tempo::Tempo,
@@ -14,8 +16,11 @@ pub(crate) enum SoundCommand {
 
 #[derive(Debug, Copy, Clone)]
 pub(crate) enum InstanceCommand {
-	PlaySound(SoundId, InstanceId, InstanceSettings),
-	SetInstanceVolume(InstanceId, f64, Option<Tween>),
+	/// Plays a sound, optionally deferring the start until a specific
+	/// sample or metronome beat instead of as soon as this command is
+	/// dequeued.
+	PlaySound(SoundId, InstanceId, InstanceSettings, Option<ClockTime>),
+	SetInstanceVolume(InstanceId, f64, Option<Tween>, Option<ClockTime>),
 	SetInstancePitch(InstanceId, f64, Option<Tween>),
 	PauseInstance(InstanceId, Option<Tween>),
 	ResumeInstance(InstanceId, Option<Tween>),
@@ -28,19 +33,21 @@ pub(crate) enum InstanceCommand {
 #[derive(Debug, Copy, Clone)]
 pub(crate) enum MetronomeCommand {
 	SetMetronomeTempo(Tempo),
-	StartMetronome,
+	StartMetronome(Option<ClockTime>),
 	PauseMetronome,
 	StopMetronome,
 }
 
 pub(crate) enum SequenceCommand<CustomEvent> {
 	StartSequence(SequenceId, Sequence<CustomEvent>),
+	StartSequenceQuantized(SequenceId, Sequence<CustomEvent>, Quantization),
 	LoopSound(SequenceId, SoundId, LoopSettings, InstanceSettings),
 	MuteSequence(SequenceId),
 	UnmuteSequence(SequenceId),
 	PauseSequence(SequenceId),
 	ResumeSequence(SequenceId),
 	StopSequence(SequenceId),
+	StopSequenceQuantized(SequenceId, Quantization),
 }
 
 pub(crate) enum Command<CustomEvent> {
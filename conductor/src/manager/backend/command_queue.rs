@@ -0,0 +1,128 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::command::Command;
+
+struct ScheduledCommand<CustomEvent> {
+	sample: u64,
+	// commands scheduled for the same sample are applied in the order
+	// they were pushed
+	sequence: u64,
+	command: Command<CustomEvent>,
+}
+
+impl<CustomEvent> PartialEq for ScheduledCommand<CustomEvent> {
+	fn eq(&self, other: &Self) -> bool {
+		self.sample == other.sample && self.sequence == other.sequence
+	}
+}
+
+impl<CustomEvent> Eq for ScheduledCommand<CustomEvent> {}
+
+impl<CustomEvent> PartialOrd for ScheduledCommand<CustomEvent> {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<CustomEvent> Ord for ScheduledCommand<CustomEvent> {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		(self.sample, self.sequence).cmp(&(other.sample, other.sequence))
+	}
+}
+
+/// A queue of commands that should each be applied at a specific sample,
+/// rather than whenever the backend happens to drain them.
+///
+/// The backend advances this queue's running sample counter one sample
+/// at a time via [`tick`](CommandQueue::tick), so a block of output can
+/// be split at the boundaries where scheduled commands land.
+pub(crate) struct CommandQueue<CustomEvent> {
+	current_sample: u64,
+	next_sequence: u64,
+	pending: BinaryHeap<Reverse<ScheduledCommand<CustomEvent>>>,
+}
+
+impl<CustomEvent> CommandQueue<CustomEvent> {
+	pub fn new() -> Self {
+		Self {
+			current_sample: 0,
+			next_sequence: 0,
+			pending: BinaryHeap::new(),
+		}
+	}
+
+	pub fn current_sample(&self) -> u64 {
+		self.current_sample
+	}
+
+	/// Schedules a command to be applied at `at_sample`, or immediately
+	/// (on the next [`tick`](CommandQueue::tick)) if `None`.
+	pub fn push(&mut self, command: Command<CustomEvent>, at_sample: Option<u64>) {
+		let sample = at_sample.unwrap_or(self.current_sample);
+		let sequence = self.next_sequence;
+		self.next_sequence += 1;
+		self.pending.push(Reverse(ScheduledCommand {
+			sample,
+			sequence,
+			command,
+		}));
+	}
+
+	/// Advances the running sample counter by one sample and pops every
+	/// command whose scheduled sample has now been reached, in the order
+	/// they should be applied. Commands that are still in the future are
+	/// left on the queue (re-held) for a later tick.
+	pub fn tick(&mut self) -> Vec<Command<CustomEvent>> {
+		self.current_sample += 1;
+		let mut ready = vec![];
+		while let Some(Reverse(scheduled)) = self.pending.peek() {
+			if scheduled.sample > self.current_sample {
+				break;
+			}
+			let Reverse(scheduled) = self.pending.pop().unwrap();
+			ready.push(scheduled.command);
+		}
+		ready
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn emit(event: i32) -> Command<i32> {
+		Command::EmitCustomEvent(event)
+	}
+
+	#[test]
+	fn commands_scheduled_for_the_same_sample_apply_in_push_order() {
+		let mut queue = CommandQueue::new();
+		queue.push(emit(1), Some(5));
+		queue.push(emit(2), Some(5));
+		queue.push(emit(0), Some(1));
+		assert!(matches!(queue.tick()[..], [Command::EmitCustomEvent(0)]));
+		for _ in 0..3 {
+			assert!(queue.tick().is_empty());
+		}
+		let ready = queue.tick();
+		assert!(matches!(ready[..], [Command::EmitCustomEvent(1), Command::EmitCustomEvent(2)]));
+	}
+
+	#[test]
+	fn commands_with_no_explicit_sample_apply_on_the_next_tick() {
+		let mut queue = CommandQueue::new();
+		queue.push(emit(0), None);
+		let ready = queue.tick();
+		assert!(matches!(ready[..], [Command::EmitCustomEvent(0)]));
+	}
+
+	#[test]
+	fn future_commands_stay_queued_until_their_sample_is_reached() {
+		let mut queue = CommandQueue::new();
+		queue.push(emit(0), Some(3));
+		assert!(queue.tick().is_empty());
+		assert!(queue.tick().is_empty());
+		assert!(matches!(queue.tick()[..], [Command::EmitCustomEvent(0)]));
+	}
+}
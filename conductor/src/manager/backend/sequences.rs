@@ -1,22 +1,43 @@
 use crate::{
+	clock::ClockTime,
 	command::InstanceCommand,
 	command::MetronomeCommand,
 	command::{Command, SequenceCommand},
 	duration::Duration,
 	instance::{InstanceId, InstanceSettings},
 	metronome::Metronome,
+	quantization::Quantization,
 	sequence::SequenceOutputCommand,
 	sequence::{Sequence, SequenceId, SequenceTask},
 };
+use super::command_queue::CommandQueue;
 use indexmap::IndexMap;
 use ringbuf::Producer;
-use std::vec::Drain;
+
+/// Returns the scheduled clock time carried by a command, if it has one.
+/// Most commands apply as soon as they're dequeued (`None`); a few
+/// support being deferred to a specific sample or metronome beat.
+fn command_time<CustomEvent>(command: &Command<CustomEvent>) -> Option<ClockTime> {
+	match command {
+		Command::Instance(InstanceCommand::PlaySound(_, _, _, time)) => *time,
+		Command::Instance(InstanceCommand::SetInstanceVolume(_, _, _, time)) => *time,
+		Command::Metronome(MetronomeCommand::StartMetronome(time)) => *time,
+		_ => None,
+	}
+}
 
 pub(crate) struct Sequences<CustomEvent> {
 	sequences: IndexMap<SequenceId, Sequence<CustomEvent>>,
 	sequences_to_remove: Vec<SequenceId>,
 	sequence_output_command_queue: Vec<SequenceOutputCommand<InstanceId, CustomEvent>>,
-	output_command_queue: Vec<Command<CustomEvent>>,
+	// commands produced by sequences, scheduled against the backend's
+	// running sample counter rather than applied the instant they're
+	// produced
+	command_queue: CommandQueue<CustomEvent>,
+	// sequences waiting for a quantized boundary to be crossed before
+	// they're started, keyed by the beat they should start on
+	pending_starts: Vec<(SequenceId, Sequence<CustomEvent>, f64)>,
+	pending_stops: Vec<(SequenceId, f64)>,
 }
 
 impl<CustomEvent: Copy> Sequences<CustomEvent> {
@@ -25,7 +46,9 @@ impl<CustomEvent: Copy> Sequences<CustomEvent> {
 			sequences: IndexMap::with_capacity(sequence_capacity),
 			sequences_to_remove: Vec::with_capacity(sequence_capacity),
 			sequence_output_command_queue: Vec::with_capacity(command_capacity),
-			output_command_queue: Vec::with_capacity(command_capacity),
+			command_queue: CommandQueue::new(),
+			pending_starts: Vec::new(),
+			pending_stops: Vec::new(),
 		}
 	}
 
@@ -34,11 +57,87 @@ impl<CustomEvent: Copy> Sequences<CustomEvent> {
 		self.sequences.insert(id, sequence);
 	}
 
+	fn quantize(&self, quantization: Quantization, metronome: &Metronome) -> f64 {
+		quantization.next_beat(metronome.current_beat(), metronome.settings.beats_per_bar)
+	}
+
+	/// Pushes `command` onto the command queue, resolving any [`ClockTime`]
+	/// it carries against the metronome's current sample-accurate clock.
+	fn schedule(&mut self, command: Command<CustomEvent>, sample_rate: u32, metronome: &Metronome) {
+		let at_sample = command_time(&command).map(|time| {
+			time.in_samples(sample_rate, metronome.settings.tempo, metronome.start_sample())
+		});
+		self.command_queue.push(command, at_sample);
+	}
+
+	/// Schedules an instance command, deferring it to the sample or
+	/// metronome beat carried by [`InstanceCommand::PlaySound`]'s or
+	/// [`InstanceCommand::SetInstanceVolume`]'s `Option<ClockTime>` field
+	/// instead of applying it on the very next tick. This is the entry
+	/// point a host-facing manager calls to play a sound (or change its
+	/// volume) at a specific point in time rather than immediately.
+	pub fn schedule_instance_command(
+		&mut self,
+		command: InstanceCommand,
+		sample_rate: u32,
+		metronome: &Metronome,
+	) {
+		self.schedule(Command::Instance(command), sample_rate, metronome);
+	}
+
+	/// Schedules a metronome command, deferring
+	/// [`MetronomeCommand::StartMetronome`] to the clock time it carries
+	/// instead of starting it on the very next tick.
+	pub fn schedule_metronome_command(
+		&mut self,
+		command: MetronomeCommand,
+		sample_rate: u32,
+		metronome: &Metronome,
+	) {
+		self.schedule(Command::Metronome(command), sample_rate, metronome);
+	}
+
+	/// Starts `sequence` the next time `quantization` is satisfied, instead
+	/// of immediately — the entry point for a clip-launch style workflow
+	/// where a host wants a sequence to start on the next beat or bar
+	/// rather than the instant the command is processed.
+	pub fn start_sequence_quantized(
+		&mut self,
+		id: SequenceId,
+		sequence: Sequence<CustomEvent>,
+		quantization: Quantization,
+		metronome: &Metronome,
+	) {
+		self.run_command(
+			SequenceCommand::StartSequenceQuantized(id, sequence, quantization),
+			metronome,
+		);
+	}
+
+	/// Stops the sequence with the given id the next time `quantization`
+	/// is satisfied, instead of immediately.
+	pub fn stop_sequence_quantized(
+		&mut self,
+		id: SequenceId,
+		quantization: Quantization,
+		metronome: &Metronome,
+	) {
+		self.run_command(SequenceCommand::StopSequenceQuantized(id, quantization), metronome);
+	}
+
 	pub fn run_command(&mut self, command: SequenceCommand<CustomEvent>, metronome: &Metronome) {
 		match command {
 			SequenceCommand::StartSequence(id, sequence) => {
 				self.start_sequence(id, sequence);
 			}
+			SequenceCommand::StartSequenceQuantized(id, sequence, quantization) => {
+				let start_beat = self.quantize(quantization, metronome);
+				self.pending_starts.push((id, sequence, start_beat));
+			}
+			SequenceCommand::StopSequenceQuantized(id, quantization) => {
+				let stop_beat = self.quantize(quantization, metronome);
+				self.pending_stops.push((id, stop_beat));
+			}
 			SequenceCommand::LoopSound(id, sound_id, loop_settings, instance_settings) => {
 				let tempo = sound_id
 					.metadata()
@@ -98,9 +197,32 @@ impl<CustomEvent: Copy> Sequences<CustomEvent> {
 	pub fn update(
 		&mut self,
 		dt: f64,
+		sample_rate: u32,
 		metronome: &Metronome,
 		sequences_to_unload_producer: &mut Producer<Sequence<CustomEvent>>,
-	) -> Drain<Command<CustomEvent>> {
+	) -> Vec<Command<CustomEvent>> {
+		// start any quantized sequences whose boundary has been crossed
+		let current_beat = metronome.current_beat();
+		let mut i = 0;
+		while i < self.pending_starts.len() {
+			if self.pending_starts[i].2 <= current_beat {
+				let (id, sequence, _) = self.pending_starts.remove(i);
+				self.start_sequence(id, sequence);
+			} else {
+				i += 1;
+			}
+		}
+		// stop any quantized sequences whose boundary has been crossed
+		self.pending_stops.retain(|(id, stop_beat)| {
+			if *stop_beat <= current_beat {
+				if let Some(sequence) = self.sequences.get_mut(id) {
+					sequence.stop();
+				}
+				false
+			} else {
+				true
+			}
+		});
 		// update sequences and collect their commands
 		for (id, sequence) in &mut self.sequences {
 			sequence.update(dt, metronome, &mut self.sequence_output_command_queue);
@@ -119,17 +241,25 @@ impl<CustomEvent: Copy> Sequences<CustomEvent> {
 			}
 		}
 		// convert sequence commands to commands that can be consumed
-		// by the backend
+		// by the backend, scheduling each one against the command queue
+		// (sequence-generated commands always apply as soon as they're
+		// produced, so they're pushed with no explicit clock time)
 		for command in self.sequence_output_command_queue.drain(..) {
-			self.output_command_queue.push(match command {
+			let command = match command {
 				SequenceOutputCommand::PlaySound(sound_id, instance_id, settings) => {
-					Command::Instance(InstanceCommand::PlaySound(sound_id, instance_id, settings))
+					Command::Instance(InstanceCommand::PlaySound(
+						sound_id,
+						instance_id,
+						settings,
+						None,
+					))
 				}
 				SequenceOutputCommand::SetInstanceVolume(instance_id, volume, tween) => {
 					Command::Instance(InstanceCommand::SetInstanceVolume(
 						instance_id,
 						volume,
 						tween,
+						None,
 					))
 				}
 				SequenceOutputCommand::SetInstancePitch(instance_id, pitch, tween) => {
@@ -159,7 +289,7 @@ impl<CustomEvent: Copy> Sequences<CustomEvent> {
 					Command::Metronome(MetronomeCommand::SetMetronomeTempo(tempo))
 				}
 				SequenceOutputCommand::StartMetronome => {
-					Command::Metronome(MetronomeCommand::StartMetronome)
+					Command::Metronome(MetronomeCommand::StartMetronome(None))
 				}
 				SequenceOutputCommand::PauseMetronome => {
 					Command::Metronome(MetronomeCommand::PauseMetronome)
@@ -168,8 +298,18 @@ impl<CustomEvent: Copy> Sequences<CustomEvent> {
 					Command::Metronome(MetronomeCommand::StopMetronome)
 				}
 				SequenceOutputCommand::EmitCustomEvent(event) => Command::EmitCustomEvent(event),
-			});
+			};
+			self.schedule(command, sample_rate, metronome);
+		}
+		// drain every command whose scheduled sample falls within this
+		// block, one sample at a time, so a command due 100 samples into
+		// the block is returned in the right order relative to ones
+		// before and after it
+		let samples_in_block = (dt * sample_rate as f64).round() as u64;
+		let mut ready = Vec::new();
+		for _ in 0..samples_in_block {
+			ready.extend(self.command_queue.tick());
 		}
-		self.output_command_queue.drain(..)
+		ready
 	}
 }
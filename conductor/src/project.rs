@@ -32,7 +32,7 @@ impl Project {
 
 	pub fn load_sound(&mut self, path: &Path) -> Result<SoundId, Box<dyn Error>> {
 		let id = SoundId::new();
-		self.sounds.insert(id, Sound::from_ogg_file(path)?);
+		self.sounds.insert(id, Sound::from_file(path)?);
 		Ok(id)
 	}
 
@@ -0,0 +1,50 @@
+/// A musical boundary that a sequence or sound's start (or stop) can be
+/// deferred to, so it lines up with the beat instead of starting the
+/// instant the command is dequeued.
+#[derive(Debug, Copy, Clone)]
+pub enum Quantization {
+	Beat,
+	HalfBeat,
+	Bar,
+	Bars(u32),
+}
+
+impl Quantization {
+	/// Returns the next beat (counted from when the metronome started)
+	/// that satisfies this quantization, at or after `current_beat`.
+	pub(crate) fn next_beat(&self, current_beat: f64, beats_per_bar: u32) -> f64 {
+		let interval = match self {
+			Self::Beat => 1.0,
+			Self::HalfBeat => 0.5,
+			Self::Bar => beats_per_bar as f64,
+			// `Bars(0)` would otherwise divide by zero below and produce a
+			// boundary that never compares `<=` true, leaving whatever's
+			// waiting on it pending forever; treat it the same as `Bars(1)`
+			Self::Bars(bars) => beats_per_bar as f64 * (*bars).max(1) as f64,
+		};
+		(current_beat / interval).ceil() * interval
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn beat_quantizes_up_to_the_next_whole_beat() {
+		assert_eq!(Quantization::Beat.next_beat(2.25, 4), 3.0);
+		assert_eq!(Quantization::Beat.next_beat(3.0, 4), 3.0);
+	}
+
+	#[test]
+	fn bar_quantizes_up_to_the_next_bar_boundary() {
+		assert_eq!(Quantization::Bar.next_beat(5.0, 4), 8.0);
+	}
+
+	#[test]
+	fn bars_zero_does_not_produce_nan_and_still_makes_progress() {
+		let next = Quantization::Bars(0).next_beat(5.0, 4);
+		assert!(next.is_finite());
+		assert!(next >= 5.0);
+	}
+}
@@ -0,0 +1,357 @@
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::{duration::Duration, frame::Frame, tempo::Tempo};
+
+/// The container format a sound was decoded from.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum SoundFormat {
+	#[cfg(feature = "audio_vorbis")]
+	Vorbis,
+	#[cfg(feature = "audio_wav")]
+	Wav,
+	#[cfg(feature = "audio_flac")]
+	Flac,
+	#[cfg(feature = "audio_mp3")]
+	Mp3,
+}
+
+impl SoundFormat {
+	/// Guesses the format of a sound file, first from its extension,
+	/// then by sniffing the first few bytes of the file if the
+	/// extension is missing or unrecognized.
+	fn from_path(path: &Path) -> Result<Self, SoundFromFileError> {
+		if let Some(format) = path.extension().and_then(OsStr::to_str).and_then(|ext| {
+			match ext.to_lowercase().as_str() {
+				#[cfg(feature = "audio_vorbis")]
+				"ogg" | "oga" => Some(Self::Vorbis),
+				#[cfg(feature = "audio_wav")]
+				"wav" | "wave" => Some(Self::Wav),
+				#[cfg(feature = "audio_flac")]
+				"flac" => Some(Self::Flac),
+				#[cfg(feature = "audio_mp3")]
+				"mp3" => Some(Self::Mp3),
+				_ => None,
+			}
+		}) {
+			return Ok(format);
+		}
+		Self::sniff(path)
+	}
+
+	/// Falls back to reading the magic bytes at the start of the file
+	/// when the extension doesn't tell us (or doesn't match a format
+	/// we were built with support for).
+	fn sniff(path: &Path) -> Result<Self, SoundFromFileError> {
+		let mut header = [0u8; 4];
+		let mut file = File::open(path)?;
+		file.read_exact(&mut header)?;
+		#[cfg(feature = "audio_vorbis")]
+		if &header == b"OggS" {
+			return Ok(Self::Vorbis);
+		}
+		#[cfg(feature = "audio_wav")]
+		if &header == b"RIFF" {
+			return Ok(Self::Wav);
+		}
+		#[cfg(feature = "audio_flac")]
+		if &header == b"fLaC" {
+			return Ok(Self::Flac);
+		}
+		#[cfg(feature = "audio_mp3")]
+		// an ID3v2 tag's 4th byte is the tag version (2, 3, or 4), not a
+		// null byte, so only the first 3 bytes are a fixed magic value
+		if &header[..3] == b"ID3" || header[0] == 0xFF && header[1] & 0xE0 == 0xE0 {
+			return Ok(Self::Mp3);
+		}
+		Err(SoundFromFileError::UnsupportedFormat)
+	}
+}
+
+#[derive(Debug)]
+pub enum SoundFromFileError {
+	/// The file's format couldn't be identified, or support for it
+	/// wasn't enabled via its `audio_*` feature.
+	UnsupportedFormat,
+	Io(std::io::Error),
+	#[cfg(feature = "audio_vorbis")]
+	Vorbis(lewton::VorbisError),
+	#[cfg(feature = "audio_wav")]
+	Wav(hound::Error),
+	#[cfg(feature = "audio_flac")]
+	Flac(claxon::Error),
+	#[cfg(feature = "audio_mp3")]
+	Mp3(minimp3::Error),
+}
+
+impl Display for SoundFromFileError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::UnsupportedFormat => f.write_str(
+				"could not identify this sound's format, or support for it was not compiled in",
+			),
+			Self::Io(error) => error.fmt(f),
+			#[cfg(feature = "audio_vorbis")]
+			Self::Vorbis(error) => error.fmt(f),
+			#[cfg(feature = "audio_wav")]
+			Self::Wav(error) => error.fmt(f),
+			#[cfg(feature = "audio_flac")]
+			Self::Flac(error) => error.fmt(f),
+			#[cfg(feature = "audio_mp3")]
+			Self::Mp3(error) => error.fmt(f),
+		}
+	}
+}
+
+impl Error for SoundFromFileError {}
+
+impl From<std::io::Error> for SoundFromFileError {
+	fn from(error: std::io::Error) -> Self {
+		Self::Io(error)
+	}
+}
+
+/// A piece of audio that can be played by an instance.
+#[derive(Debug, Clone)]
+pub struct Sound {
+	pub sample_rate: u32,
+	frames: Vec<Frame>,
+	/// The tempo to use for sequences that loop this sound, if
+	/// one isn't specified explicitly.
+	pub tempo: Option<Tempo>,
+	/// The musically-meaningful duration of this sound (which may be
+	/// shorter than the raw sample data, e.g. to trim trailing silence),
+	/// used when looping.
+	pub semantic_duration: Option<Duration>,
+}
+
+impl Sound {
+	fn from_frames(sample_rate: u32, frames: Vec<Frame>) -> Self {
+		Self::from_frames_with_metadata(sample_rate, frames, None, None)
+	}
+
+	fn from_frames_with_metadata(
+		sample_rate: u32,
+		frames: Vec<Frame>,
+		tempo: Option<Tempo>,
+		semantic_duration: Option<Duration>,
+	) -> Self {
+		Self {
+			sample_rate,
+			frames,
+			tempo,
+			semantic_duration,
+		}
+	}
+
+	/// Loads a sound from a file, figuring out its format from its
+	/// extension or, failing that, its contents.
+	///
+	/// The formats that are actually supported depend on which of the
+	/// `audio_wav`, `audio_flac`, `audio_mp3`, and `audio_vorbis`
+	/// feature flags are enabled.
+	pub fn from_file(path: impl AsRef<Path>) -> Result<Self, SoundFromFileError> {
+		let path = path.as_ref();
+		match SoundFormat::from_path(path)? {
+			#[cfg(feature = "audio_vorbis")]
+			SoundFormat::Vorbis => Self::from_ogg_file(path),
+			#[cfg(feature = "audio_wav")]
+			SoundFormat::Wav => Self::from_wav_file(path),
+			#[cfg(feature = "audio_flac")]
+			SoundFormat::Flac => Self::from_flac_file(path),
+			#[cfg(feature = "audio_mp3")]
+			SoundFormat::Mp3 => Self::from_mp3_file(path),
+		}
+	}
+
+	#[cfg(feature = "audio_vorbis")]
+	pub fn from_ogg_file(path: impl AsRef<Path>) -> Result<Self, SoundFromFileError> {
+		use lewton::inside_ogg::OggStreamReader;
+		let mut reader =
+			OggStreamReader::new(File::open(path)?).map_err(SoundFromFileError::Vorbis)?;
+		let sample_rate = reader.ident_hdr.audio_sample_rate;
+		let channels = reader.ident_hdr.audio_channels;
+		let tempo = tempo_from_vorbis_comments(&reader.comment_hdr.comment_list);
+		let semantic_duration =
+			loop_length_from_vorbis_comments(&reader.comment_hdr.comment_list, sample_rate);
+		let mut frames = vec![];
+		while let Some(packet) = reader
+			.read_dec_packet_itl()
+			.map_err(SoundFromFileError::Vorbis)?
+		{
+			push_interleaved_samples(&mut frames, &packet, channels as usize, |sample| {
+				sample as f32 / std::i16::MAX as f32
+			});
+		}
+		Ok(Self::from_frames_with_metadata(
+			sample_rate,
+			frames,
+			tempo,
+			semantic_duration,
+		))
+	}
+
+	#[cfg(feature = "audio_wav")]
+	pub fn from_wav_file(path: impl AsRef<Path>) -> Result<Self, SoundFromFileError> {
+		let mut reader = hound::WavReader::open(path).map_err(SoundFromFileError::Wav)?;
+		let spec = reader.spec();
+		let mut frames = vec![];
+		match spec.sample_format {
+			hound::SampleFormat::Float => {
+				let samples: Vec<f32> = reader
+					.samples::<f32>()
+					.collect::<Result<_, _>>()
+					.map_err(SoundFromFileError::Wav)?;
+				push_interleaved_samples(&mut frames, &samples, spec.channels as usize, |s| s);
+			}
+			hound::SampleFormat::Int => {
+				let max = (1i64 << (spec.bits_per_sample - 1)) as f32;
+				let samples: Vec<i32> = reader
+					.samples::<i32>()
+					.collect::<Result<_, _>>()
+					.map_err(SoundFromFileError::Wav)?;
+				push_interleaved_samples(&mut frames, &samples, spec.channels as usize, |s| {
+					s as f32 / max
+				});
+			}
+		}
+		Ok(Self::from_frames(spec.sample_rate, frames))
+	}
+
+	#[cfg(feature = "audio_flac")]
+	pub fn from_flac_file(path: impl AsRef<Path>) -> Result<Self, SoundFromFileError> {
+		let mut reader = claxon::FlacReader::open(path).map_err(SoundFromFileError::Flac)?;
+		let info = reader.streaminfo();
+		let max = (1i64 << (info.bits_per_sample - 1)) as f32;
+		let samples: Vec<i32> = reader
+			.samples()
+			.collect::<Result<_, _>>()
+			.map_err(SoundFromFileError::Flac)?;
+		let mut frames = vec![];
+		push_interleaved_samples(&mut frames, &samples, info.channels as usize, |s| {
+			s as f32 / max
+		});
+		Ok(Self::from_frames(info.sample_rate, frames))
+	}
+
+	#[cfg(feature = "audio_mp3")]
+	pub fn from_mp3_file(path: impl AsRef<Path>) -> Result<Self, SoundFromFileError> {
+		let mut decoder = minimp3::Decoder::new(File::open(path)?);
+		let mut sample_rate = 0;
+		let mut frames = vec![];
+		loop {
+			match decoder.next_frame() {
+				Ok(frame) => {
+					sample_rate = frame.sample_rate as u32;
+					push_interleaved_samples(&mut frames, &frame.data, frame.channels, |s| {
+						s as f32 / std::i16::MAX as f32
+					});
+				}
+				Err(minimp3::Error::Eof) => break,
+				Err(error) => return Err(SoundFromFileError::Mp3(error)),
+			}
+		}
+		Ok(Self::from_frames(sample_rate, frames))
+	}
+
+	pub fn duration(&self) -> f64 {
+		self.frames.len() as f64 / self.sample_rate as f64
+	}
+
+	pub(crate) fn frame_at_index(&self, index: usize) -> Option<Frame> {
+		self.frames.get(index).copied()
+	}
+}
+
+/// Looks for a `TEMPO` Vorbis comment (the convention used by loop-tagged
+/// game music to carry the sound's tempo) and parses it as beats per
+/// minute.
+#[cfg(feature = "audio_vorbis")]
+fn tempo_from_vorbis_comments(comments: &[(String, String)]) -> Option<Tempo> {
+	comments
+		.iter()
+		.find(|(key, _)| key.eq_ignore_ascii_case("TEMPO"))
+		.and_then(|(_, value)| value.parse::<f64>().ok())
+		.map(Tempo::from)
+}
+
+/// Looks for a `LOOPLENGTH` Vorbis comment (the loop length in samples,
+/// the convention used by loop-tagged game music) and converts it to a
+/// semantic duration.
+#[cfg(feature = "audio_vorbis")]
+fn loop_length_from_vorbis_comments(
+	comments: &[(String, String)],
+	sample_rate: u32,
+) -> Option<Duration> {
+	comments
+		.iter()
+		.find(|(key, _)| key.eq_ignore_ascii_case("LOOPLENGTH"))
+		.and_then(|(_, value)| value.parse::<f64>().ok())
+		.map(|length_in_samples| Duration::Seconds(length_in_samples / sample_rate as f64))
+}
+
+/// Converts a slice of interleaved samples in an arbitrary channel
+/// count to stereo `Frame`s, pushing them onto `frames`.
+fn push_interleaved_samples<T: Copy>(
+	frames: &mut Vec<Frame>,
+	samples: &[T],
+	channels: usize,
+	to_f32: impl Fn(T) -> f32,
+) {
+	if channels == 1 {
+		frames.extend(samples.iter().map(|sample| {
+			let sample = to_f32(*sample);
+			Frame::new(sample, sample)
+		}));
+	} else {
+		frames.extend(samples.chunks_exact(channels).map(|chunk| {
+			Frame::new(to_f32(chunk[0]), to_f32(chunk[1]))
+		}));
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[cfg(feature = "audio_mp3")]
+	#[test]
+	fn an_id3v2_tagged_header_is_recognized_regardless_of_tag_version() {
+		for version in [2u8, 3, 4] {
+			let path = std::env::temp_dir().join(format!("conductor_id3v2_{}_sniff_test", version));
+			std::fs::write(&path, [b'I', b'D', b'3', version]).unwrap();
+			let format = SoundFormat::sniff(&path);
+			std::fs::remove_file(&path).ok();
+			assert!(matches!(format, Ok(SoundFormat::Mp3)));
+		}
+	}
+
+	#[cfg(feature = "audio_vorbis")]
+	#[test]
+	fn tempo_is_read_from_a_tempo_comment_case_insensitively() {
+		let comments = vec![("tempo".to_string(), "128".to_string())];
+		assert_eq!(tempo_from_vorbis_comments(&comments), Some(Tempo(128.0)));
+	}
+
+	#[cfg(feature = "audio_vorbis")]
+	#[test]
+	fn tempo_is_none_when_no_tempo_comment_is_present() {
+		let comments = vec![("artist".to_string(), "nobody".to_string())];
+		assert_eq!(tempo_from_vorbis_comments(&comments), None);
+	}
+
+	#[cfg(feature = "audio_vorbis")]
+	#[test]
+	fn loop_length_is_converted_from_samples_to_seconds() {
+		let comments = vec![("LOOPLENGTH".to_string(), "44100".to_string())];
+		let semantic_duration = loop_length_from_vorbis_comments(&comments, 44100);
+		match semantic_duration {
+			Some(Duration::Seconds(seconds)) => assert!((seconds - 1.0).abs() < 1e-9),
+			None => panic!("expected a semantic duration to be derived from LOOPLENGTH"),
+		}
+	}
+}
@@ -0,0 +1,19 @@
+/// A tempo, measured in beats per minute.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Tempo(pub f64);
+
+impl Tempo {
+	pub fn seconds_per_beat(&self) -> f64 {
+		60.0 / self.0
+	}
+
+	pub fn beats_to_seconds(&self, beats: f64) -> f64 {
+		beats * self.seconds_per_beat()
+	}
+}
+
+impl From<f64> for Tempo {
+	fn from(bpm: f64) -> Self {
+		Self(bpm)
+	}
+}
@@ -0,0 +1,40 @@
+use crate::modulator::{Modulator, ModulatorHandle, ModulatorId, ModulatorSettings, Modulators};
+
+/// Owns every modulator created through this manager and drives them from
+/// the audio thread.
+///
+/// Only the modulator bookkeeping lives here for now; sound loading and
+/// playback are driven through [`Handle`](crate::sound::handle::Handle)
+/// implementations created elsewhere.
+pub struct AudioManager {
+    modulators: Modulators,
+}
+
+impl AudioManager {
+    pub fn new() -> Self {
+        Self {
+            modulators: Modulators::new(),
+        }
+    }
+
+    /// Creates a modulator and returns a handle that can be bound to any
+    /// instance parameter accepting a [`ParameterInput`](crate::parameter_input::ParameterInput).
+    pub fn add_modulator(&mut self, settings: ModulatorSettings) -> ModulatorHandle {
+        let id = ModulatorId::new();
+        self.modulators.add(id, Modulator::new(settings));
+        ModulatorHandle::new(id)
+    }
+
+    /// Advances every modulator by one audio callback's worth of time.
+    /// Called once per callback, before sounds resolve their parameters
+    /// against [`Modulators`].
+    pub fn on_start_processing(&mut self, dt: f64) {
+        self.modulators.update(dt);
+    }
+}
+
+impl Default for AudioManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,18 @@
+use super::ModulatorId;
+
+/// A handle to a modulator, used to bind it to instance parameters via
+/// [`Handle::set_volume`](crate::sound::handle::Handle::set_volume) and
+/// friends.
+pub struct ModulatorHandle {
+    id: ModulatorId,
+}
+
+impl ModulatorHandle {
+    pub(crate) fn new(id: ModulatorId) -> Self {
+        Self { id }
+    }
+
+    pub fn id(&self) -> ModulatorId {
+        self.id
+    }
+}
@@ -0,0 +1,310 @@
+mod handle;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub use handle::ModulatorHandle;
+
+static NEXT_MODULATOR_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A unique identifier for a [`Modulator`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ModulatorId(usize);
+
+impl ModulatorId {
+    pub(crate) fn new() -> Self {
+        Self(NEXT_MODULATOR_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// The shape an [`LfoSettings`] oscillates in.
+#[derive(Debug, Copy, Clone)]
+pub enum Waveform {
+    Sine,
+    Triangle,
+    Square,
+}
+
+impl Waveform {
+    fn sample(&self, phase: f64) -> f64 {
+        match self {
+            Self::Sine => (phase * std::f64::consts::TAU).sin(),
+            Self::Triangle => 4.0 * (phase - (phase + 0.75).floor() + 0.25).abs() - 1.0,
+            Self::Square => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+        }
+    }
+}
+
+/// How often an [`LfoSettings`] completes a cycle.
+#[derive(Debug, Copy, Clone)]
+pub enum LfoFrequency {
+    /// A fixed number of cycles per second.
+    Hz(f64),
+    /// A number of cycles per metronome beat.
+    ///
+    /// A modulator has no access to a metronome's tempo, so this value is
+    /// evaluated as cycles per second, the same as [`LfoFrequency::Hz`].
+    Beats(f64),
+}
+
+/// Settings for an LFO modulator, which continuously oscillates between
+/// `1.0 - depth` and `1.0 + depth` to drive vibrato, tremolo, and similar
+/// effects.
+#[derive(Debug, Copy, Clone)]
+pub struct LfoSettings {
+    pub waveform: Waveform,
+    pub frequency: LfoFrequency,
+    pub depth: f64,
+    /// How long to wait (in seconds) before the LFO starts oscillating.
+    pub delay: f64,
+}
+
+impl Default for LfoSettings {
+    fn default() -> Self {
+        Self {
+            waveform: Waveform::Sine,
+            frequency: LfoFrequency::Hz(5.0),
+            depth: 0.1,
+            delay: 0.0,
+        }
+    }
+}
+
+/// How a single [`EnvelopeSegment`] interpolates between its start and
+/// end values.
+#[derive(Debug, Copy, Clone)]
+pub enum EnvelopeCurve {
+    Linear,
+    EaseIn,
+    EaseOut,
+}
+
+impl EnvelopeCurve {
+    fn apply(&self, t: f64) -> f64 {
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => t * (2.0 - t),
+        }
+    }
+}
+
+/// One stage of an [`EnvelopeSettings`]: ramps from the previous stage's
+/// value (or `0.0` for the first stage) to `value` over `duration` seconds.
+#[derive(Debug, Copy, Clone)]
+pub struct EnvelopeSegment {
+    pub value: f64,
+    pub duration: f64,
+    pub curve: EnvelopeCurve,
+}
+
+/// Settings for a multi-stage envelope modulator.
+#[derive(Debug, Clone)]
+pub struct EnvelopeSettings {
+    pub segments: Vec<EnvelopeSegment>,
+    /// If set, playback loops back to this segment index once the last
+    /// segment finishes, instead of holding the final value.
+    pub loop_start: Option<usize>,
+}
+
+enum ModulatorKind {
+    Lfo(LfoSettings),
+    Envelope(EnvelopeSettings),
+}
+
+/// Settings used to create a [`Modulator`] via
+/// [`AudioManager::add_modulator`](crate::manager::AudioManager::add_modulator).
+pub enum ModulatorSettings {
+    Lfo(LfoSettings),
+    Envelope(EnvelopeSettings),
+}
+
+/// A source of continuous automation for an instance parameter.
+///
+/// Unlike a [`Tween`](crate::tween::Tween), a modulator doesn't settle on
+/// a final value — it keeps evaluating for as long as something is bound
+/// to it.
+pub(crate) struct Modulator {
+    kind: ModulatorKind,
+    time: f64,
+    current_segment: usize,
+    // the value the current envelope segment is ramping from; tracked
+    // explicitly (rather than read back off the previous segment in the
+    // list) so looping back to an earlier segment continues smoothly
+    // instead of jumping back to that segment's pre-loop starting value
+    segment_start_value: f64,
+}
+
+impl Modulator {
+    pub fn new(settings: ModulatorSettings) -> Self {
+        match settings {
+            ModulatorSettings::Lfo(settings) => Self::lfo(settings),
+            ModulatorSettings::Envelope(settings) => Self::envelope(settings),
+        }
+    }
+
+    pub fn lfo(settings: LfoSettings) -> Self {
+        Self {
+            kind: ModulatorKind::Lfo(settings),
+            time: 0.0,
+            current_segment: 0,
+            segment_start_value: 0.0,
+        }
+    }
+
+    pub fn envelope(settings: EnvelopeSettings) -> Self {
+        Self {
+            kind: ModulatorKind::Envelope(settings),
+            time: 0.0,
+            current_segment: 0,
+            segment_start_value: 0.0,
+        }
+    }
+
+    /// Advances the modulator by `dt` seconds and returns its current
+    /// value, a factor around `1.0`. A parameter bound to a modulator
+    /// takes this value directly; it is not multiplied with a separate
+    /// base value.
+    ///
+    /// For an envelope, `self.time` tracks elapsed time *within
+    /// `current_segment`*, not since the envelope started — it's reduced
+    /// by a segment's duration every time playback advances past it
+    /// (forward or looping back), so a later call resumes the interpolation
+    /// fraction from where the current segment actually left off instead
+    /// of recomputing it against the envelope's total running time.
+    pub fn update(&mut self, dt: f64) -> f64 {
+        self.time += dt;
+        match &self.kind {
+            ModulatorKind::Lfo(settings) => {
+                if self.time < settings.delay {
+                    return 1.0;
+                }
+                let frequency_hz = match settings.frequency {
+                    LfoFrequency::Hz(hz) => hz,
+                    // no tempo is known here, so a beat-synced frequency
+                    // is used as if it were given in Hz
+                    LfoFrequency::Beats(cycles) => cycles,
+                };
+                let phase = ((self.time - settings.delay) * frequency_hz).fract();
+                1.0 + settings.waveform.sample(phase) * settings.depth
+            }
+            ModulatorKind::Envelope(settings) => {
+                if settings.segments.is_empty() {
+                    return 1.0;
+                }
+                let mut elapsed = self.time;
+                loop {
+                    let segment = &settings.segments[self.current_segment];
+                    if elapsed < segment.duration {
+                        self.time = elapsed;
+                        let t = segment.curve.apply(elapsed / segment.duration.max(1e-9));
+                        return self.segment_start_value + (segment.value - self.segment_start_value) * t;
+                    }
+                    elapsed -= segment.duration;
+                    self.segment_start_value = segment.value;
+                    if self.current_segment + 1 < settings.segments.len() {
+                        self.current_segment += 1;
+                    } else if let Some(loop_start) = settings.loop_start {
+                        self.current_segment = loop_start;
+                    } else {
+                        self.time = elapsed;
+                        return segment.value;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Owns every [`Modulator`] created through
+/// [`AudioManager::add_modulator`](crate::manager::AudioManager::add_modulator)
+/// and advances them once per audio callback.
+#[derive(Default)]
+pub(crate) struct Modulators {
+    modulators: std::collections::HashMap<ModulatorId, Modulator>,
+    values: std::collections::HashMap<ModulatorId, f64>,
+}
+
+impl Modulators {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, id: ModulatorId, modulator: Modulator) {
+        self.values.insert(id, 1.0);
+        self.modulators.insert(id, modulator);
+    }
+
+    /// Advances every modulator by `dt` seconds and caches its new value.
+    pub fn update(&mut self, dt: f64) {
+        for (id, modulator) in self.modulators.iter_mut() {
+            self.values.insert(*id, modulator.update(dt));
+        }
+    }
+
+    /// Returns the most recently computed value of the modulator with the
+    /// given id, or `1.0` (a no-op multiplier) if it no longer exists.
+    pub fn value(&self, id: ModulatorId) -> f64 {
+        self.values.get(&id).copied().unwrap_or(1.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn three_segment_envelope() -> EnvelopeSettings {
+        EnvelopeSettings {
+            segments: vec![
+                EnvelopeSegment { value: 1.0, duration: 1.0, curve: EnvelopeCurve::Linear },
+                EnvelopeSegment { value: 0.0, duration: 5.0, curve: EnvelopeCurve::Linear },
+                EnvelopeSegment { value: 1.0, duration: 1.0, curve: EnvelopeCurve::Linear },
+            ],
+            loop_start: None,
+        }
+    }
+
+    #[test]
+    fn envelope_tracks_elapsed_within_the_current_segment() {
+        let mut modulator = Modulator::envelope(three_segment_envelope());
+        // 1.1s total: 0.1s into the second segment (1.0 -> 0.0 over 5s)
+        assert!((modulator.update(1.1) - 0.98).abs() < 1e-9);
+        // one more call landing at 1.6s total (0.6s into the second segment)
+        assert!((modulator.update(0.5) - 0.88).abs() < 1e-9);
+        // advancing to 5.0s total (4.0s into the second segment) must not
+        // have skipped ahead into the third segment, which only starts at
+        // 6.0s total
+        assert!((modulator.update(3.4) - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn envelope_loops_back_without_a_discontinuity() {
+        let settings = EnvelopeSettings {
+            segments: vec![
+                EnvelopeSegment { value: 1.0, duration: 1.0, curve: EnvelopeCurve::Linear },
+                EnvelopeSegment { value: 0.5, duration: 1.0, curve: EnvelopeCurve::Linear },
+            ],
+            loop_start: Some(1),
+        };
+        let mut modulator = Modulator::envelope(settings);
+        modulator.update(2.0); // finish segment 0, then segment 1 entirely
+        // looping back to segment 1 should hold at 0.5, not jump back to 1.0
+        assert!((modulator.update(0.0) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn lfo_oscillates_around_unity_gain() {
+        let mut modulator = Modulator::lfo(LfoSettings {
+            waveform: Waveform::Sine,
+            frequency: LfoFrequency::Hz(1.0),
+            depth: 1.0,
+            delay: 0.0,
+        });
+        assert!((modulator.update(0.25) - 2.0).abs() < 1e-9);
+    }
+}
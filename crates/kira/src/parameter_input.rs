@@ -0,0 +1,32 @@
+use crate::{modulator::ModulatorId, PlaybackRate, Volume};
+
+/// Something that can drive a sound parameter: either a fixed value that
+/// can be tweened to, or a modulator that continuously updates it.
+pub enum ParameterInput<T> {
+    Fixed(T),
+    Modulator(ModulatorId),
+}
+
+impl<T> From<T> for ParameterInput<T> {
+    fn from(value: T) -> Self {
+        Self::Fixed(value)
+    }
+}
+
+impl From<ModulatorId> for ParameterInput<Volume> {
+    fn from(id: ModulatorId) -> Self {
+        Self::Modulator(id)
+    }
+}
+
+impl From<ModulatorId> for ParameterInput<PlaybackRate> {
+    fn from(id: ModulatorId) -> Self {
+        Self::Modulator(id)
+    }
+}
+
+impl From<ModulatorId> for ParameterInput<f64> {
+    fn from(id: ModulatorId) -> Self {
+        Self::Modulator(id)
+    }
+}
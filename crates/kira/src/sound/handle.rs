@@ -1,3 +1,4 @@
+use crate::parameter_input::ParameterInput;
 use crate::tween::Tween;
 use crate::{CommandError, PlaybackRate, Volume};
 use super::static_sound::PlaybackState;
@@ -11,9 +12,15 @@ pub trait Handle {
     fn position(&self) -> f64;
 
     /// Sets the volume of the sound (as a factor of the original volume).
+    ///
+    /// Accepts either a fixed value to tween to, or a
+    /// [`ModulatorHandle`](crate::modulator::ModulatorHandle) (via
+    /// [`ModulatorHandle::id`](crate::modulator::ModulatorHandle::id)) to
+    /// continuously drive the volume from instead; in that case `tween`
+    /// is ignored.
     fn set_volume(
         &mut self,
-        volume: impl Into<Volume>,
+        volume: impl Into<ParameterInput<Volume>>,
         tween: Tween,
     ) -> Result<(), CommandError>;
 
@@ -21,15 +28,25 @@ pub trait Handle {
     ///
     /// Changing the playback rate will change both the speed
     /// and pitch of the sound.
+    ///
+    /// Accepts either a fixed value to tween to, or a bound modulator
+    /// (see [`set_volume`](Handle::set_volume)).
     fn set_playback_rate(
         &mut self,
-        playback_rate: impl Into<PlaybackRate>,
+        playback_rate: impl Into<ParameterInput<PlaybackRate>>,
         tween: Tween,
     ) -> Result<(), CommandError>;
 
     /// Sets the panning of the sound, where `0.0` is hard left,
     /// `0.5` is center, and `1.0` is hard right.
-    fn set_panning(&mut self, panning: f64, tween: Tween) -> Result<(), CommandError>;
+    ///
+    /// Accepts either a fixed value to tween to, or a bound modulator
+    /// (see [`set_volume`](Handle::set_volume)).
+    fn set_panning(
+        &mut self,
+        panning: impl Into<ParameterInput<f64>>,
+        tween: Tween,
+    ) -> Result<(), CommandError>;
 
     /// Fades out the sound to silence with the given tween and then
     /// pauses playback.
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use ringbuf::HeapProducer;
 
+use crate::parameter_input::ParameterInput;
 use crate::{tween::Tween, CommandError, PlaybackRate, Volume};
 use crate::sound::handle::Handle;
 
@@ -29,7 +30,7 @@ impl Handle for StaticSoundHandle {
 	/// Sets the volume of the sound (as a factor of the original volume).
 	fn set_volume(
 		&mut self,
-		volume: impl Into<Volume>,
+		volume: impl Into<ParameterInput<Volume>>,
 		tween: Tween,
 	) -> Result<(), CommandError> {
 		self.command_producer
@@ -43,7 +44,7 @@ impl Handle for StaticSoundHandle {
 	/// and pitch of the sound.
 	fn set_playback_rate(
 		&mut self,
-		playback_rate: impl Into<PlaybackRate>,
+		playback_rate: impl Into<ParameterInput<PlaybackRate>>,
 		tween: Tween,
 	) -> Result<(), CommandError> {
 		self.command_producer
@@ -53,9 +54,13 @@ impl Handle for StaticSoundHandle {
 
 	/// Sets the panning of the sound, where `0.0` is hard left,
 	/// `0.5` is center, and `1.0` is hard right.
-	fn set_panning(&mut self, panning: f64, tween: Tween) -> Result<(), CommandError> {
+	fn set_panning(
+		&mut self,
+		panning: impl Into<ParameterInput<f64>>,
+		tween: Tween,
+	) -> Result<(), CommandError> {
 		self.command_producer
-			.push(Command::SetPanning(panning, tween))
+			.push(Command::SetPanning(panning.into(), tween))
 			.map_err(|_| CommandError::CommandQueueFull)
 	}
 
@@ -0,0 +1,26 @@
+pub mod handle;
+pub(crate) mod sound;
+
+pub use handle::StaticSoundHandle;
+pub use sound::StaticSound;
+
+use crate::{parameter_input::ParameterInput, tween::Tween, PlaybackRate, Volume};
+
+/// The current playback state of a [`StaticSound`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PlaybackState {
+    Playing,
+    Paused,
+    Stopped,
+}
+
+pub(crate) enum Command {
+    SetVolume(ParameterInput<Volume>, Tween),
+    SetPlaybackRate(ParameterInput<PlaybackRate>, Tween),
+    SetPanning(ParameterInput<f64>, Tween),
+    Pause(Tween),
+    Resume(Tween),
+    Stop(Tween),
+    SeekTo(f64),
+    SeekBy(f64),
+}
@@ -0,0 +1,117 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+use ringbuf::HeapConsumer;
+
+use crate::{modulator::Modulators, parameter_input::ParameterInput, tween::Tween, PlaybackRate, Volume};
+
+use super::{Command, PlaybackState};
+
+/// Playback state shared between a [`StaticSound`] (on the audio thread)
+/// and its [`StaticSoundHandle`](super::StaticSoundHandle).
+pub(crate) struct Shared {
+    state: AtomicU8,
+    position: std::sync::atomic::AtomicU64,
+}
+
+impl Shared {
+    pub fn state(&self) -> PlaybackState {
+        match self.state.load(Ordering::SeqCst) {
+            0 => PlaybackState::Playing,
+            1 => PlaybackState::Paused,
+            _ => PlaybackState::Stopped,
+        }
+    }
+
+    pub fn position(&self) -> f64 {
+        f64::from_bits(self.position.load(Ordering::SeqCst))
+    }
+}
+
+/// The audio-thread side of a playing static sound.
+pub(crate) struct StaticSound {
+    command_consumer: HeapConsumer<Command>,
+    shared: Arc<Shared>,
+    volume: ParameterInput<Volume>,
+    volume_tween: Option<Tween>,
+    playback_rate: ParameterInput<PlaybackRate>,
+    playback_rate_tween: Option<Tween>,
+    panning: ParameterInput<f64>,
+    panning_tween: Option<Tween>,
+}
+
+impl StaticSound {
+    pub fn new(command_consumer: HeapConsumer<Command>, shared: Arc<Shared>) -> Self {
+        Self {
+            command_consumer,
+            shared,
+            volume: ParameterInput::Fixed(Volume::from(1.0)),
+            volume_tween: None,
+            playback_rate: ParameterInput::Fixed(PlaybackRate::from(1.0)),
+            playback_rate_tween: None,
+            panning: ParameterInput::Fixed(0.5),
+            panning_tween: None,
+        }
+    }
+
+    fn run_commands(&mut self) {
+        while let Some(command) = self.command_consumer.pop() {
+            match command {
+                Command::SetVolume(volume, tween) => {
+                    self.volume = volume;
+                    self.volume_tween = Some(tween);
+                }
+                Command::SetPlaybackRate(playback_rate, tween) => {
+                    self.playback_rate = playback_rate;
+                    self.playback_rate_tween = Some(tween);
+                }
+                Command::SetPanning(panning, tween) => {
+                    self.panning = panning;
+                    self.panning_tween = Some(tween);
+                }
+                Command::Pause(_) | Command::Resume(_) | Command::Stop(_) => {
+                    // these are consumed without changing the playback
+                    // state; nothing else handles them either
+                }
+                Command::SeekTo(_) | Command::SeekBy(_) => {}
+            }
+        }
+    }
+
+    /// Resolves the current volume, evaluating the bound modulator (if
+    /// one is bound) against `modulators` instead of using a fixed value.
+    fn current_volume(&self, modulators: &Modulators) -> f64 {
+        match self.volume {
+            ParameterInput::Fixed(volume) => volume.as_amplitude(),
+            ParameterInput::Modulator(id) => modulators.value(id),
+        }
+    }
+
+    fn current_playback_rate(&self, modulators: &Modulators) -> f64 {
+        match self.playback_rate {
+            ParameterInput::Fixed(playback_rate) => playback_rate.as_factor(),
+            ParameterInput::Modulator(id) => modulators.value(id),
+        }
+    }
+
+    fn current_panning(&self, modulators: &Modulators) -> f64 {
+        match self.panning {
+            ParameterInput::Fixed(panning) => panning,
+            ParameterInput::Modulator(id) => modulators.value(id),
+        }
+    }
+
+    /// Advances this sound by one sample, resolving its volume, playback
+    /// rate, and panning against any modulators they're bound to.
+    ///
+    /// A fixed value takes effect at once; the tween sent with it is
+    /// stored but not applied.
+    pub fn process(&mut self, modulators: &Modulators) -> (f64, f64, f64) {
+        self.run_commands();
+        (
+            self.current_volume(modulators),
+            self.current_playback_rate(modulators),
+            self.current_panning(modulators),
+        )
+    }
+}
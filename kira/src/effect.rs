@@ -0,0 +1,124 @@
+use crate::frame::Frame;
+
+/// A processing step that can be inserted into a [`Track`](crate::track::Track)'s
+/// effect chain.
+///
+/// Effects are evaluated in order, once per sample, between the track's
+/// instances being summed and the track's volume/panning being applied.
+pub trait Effect: Send {
+	fn process(&mut self, input: Frame, dt: f64) -> Frame;
+}
+
+/// A simple multiplicative gain stage.
+pub struct Gain(pub f64);
+
+impl Effect for Gain {
+	fn process(&mut self, input: Frame, _dt: f64) -> Frame {
+		input * self.0
+	}
+}
+
+/// The shape of filtering a [`Biquad`] performs.
+#[derive(Debug, Copy, Clone)]
+pub enum BiquadKind {
+	LowPass,
+	HighPass,
+}
+
+/// A two-pole, two-zero IIR filter, useful for basic low-pass/high-pass
+/// shaping in a track's effect chain.
+pub struct Biquad {
+	kind: BiquadKind,
+	cutoff_hz: f64,
+	q: f64,
+	// [x1, x2, y1, y2] per channel, kept in f64 so rounding in the
+	// feedback path doesn't shift the filter's gain
+	state: [[f64; 2]; 4],
+}
+
+impl Biquad {
+	pub fn new(kind: BiquadKind, cutoff_hz: f64, q: f64) -> Self {
+		Self {
+			kind,
+			cutoff_hz,
+			q,
+			state: [[0.0; 2]; 4],
+		}
+	}
+
+	fn coefficients(&self, sample_rate: f64) -> (f64, f64, f64, f64, f64, f64) {
+		let omega = 2.0 * std::f64::consts::PI * self.cutoff_hz / sample_rate;
+		let (sin_omega, cos_omega) = omega.sin_cos();
+		let alpha = sin_omega / (2.0 * self.q);
+		match self.kind {
+			BiquadKind::LowPass => {
+				let b1 = 1.0 - cos_omega;
+				let b0 = b1 / 2.0;
+				let b2 = b0;
+				let a0 = 1.0 + alpha;
+				let a1 = -2.0 * cos_omega;
+				let a2 = 1.0 - alpha;
+				(b0, b1, b2, a0, a1, a2)
+			}
+			BiquadKind::HighPass => {
+				let b1 = -(1.0 + cos_omega);
+				let b0 = -b1 / 2.0;
+				let b2 = b0;
+				let a0 = 1.0 + alpha;
+				let a1 = -2.0 * cos_omega;
+				let a2 = 1.0 - alpha;
+				(b0, b1, b2, a0, a1, a2)
+			}
+		}
+	}
+}
+
+impl Effect for Biquad {
+	fn process(&mut self, input: Frame, dt: f64) -> Frame {
+		let sample_rate = 1.0 / dt;
+		let (b0, b1, b2, a0, a1, a2) = self.coefficients(sample_rate);
+		let [x1, x2, y1, y2] = self.state;
+		let x = [input.left as f64, input.right as f64];
+		let mut y = [0.0; 2];
+		for channel in 0..2 {
+			y[channel] = (b0 * x[channel] + b1 * x1[channel] + b2 * x2[channel]
+				- a1 * y1[channel]
+				- a2 * y2[channel])
+				/ a0;
+		}
+		self.state = [x, x1, y, y1];
+		Frame::new(y[0] as f32, y[1] as f32)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn gain_scales_both_channels() {
+		let mut gain = Gain(0.5);
+		let output = gain.process(Frame::new(1.0, -1.0), 1.0 / 44100.0);
+		assert_eq!(output, Frame::new(0.5, -0.5));
+	}
+
+	#[test]
+	fn low_pass_settles_to_unity_gain_on_a_constant_input() {
+		let mut filter = Biquad::new(BiquadKind::LowPass, 500.0, 0.707);
+		let mut output = Frame::from_mono(0.0);
+		for _ in 0..10_000 {
+			output = filter.process(Frame::from_mono(1.0), 1.0 / 44100.0);
+		}
+		assert!((output.left - 1.0).abs() < 1e-6);
+	}
+
+	#[test]
+	fn high_pass_rejects_a_constant_input() {
+		let mut filter = Biquad::new(BiquadKind::HighPass, 500.0, 0.707);
+		let mut output = Frame::from_mono(0.0);
+		for _ in 0..10_000 {
+			output = filter.process(Frame::from_mono(1.0), 1.0 / 44100.0);
+		}
+		assert!(output.left.abs() < 1e-6);
+	}
+}
@@ -0,0 +1,81 @@
+use std::ops::{Add, Mul, Sub};
+
+/// A single sample of stereo audio.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Frame {
+	pub left: f32,
+	pub right: f32,
+}
+
+impl Frame {
+	pub fn new(left: f32, right: f32) -> Self {
+		Self { left, right }
+	}
+
+	pub fn from_mono(sample: f32) -> Self {
+		Self::new(sample, sample)
+	}
+
+	/// Pans this frame using an equal-power law, where `panning` ranges
+	/// from `0.0` (hard left) to `1.0` (hard right), with `0.5` as center.
+	/// Center scales both channels by `1/√2` (about -3 dB), which keeps
+	/// the total power constant; a linear crossfade would instead halve
+	/// them, producing a "hole in the middle".
+	pub fn panned(&self, panning: f64) -> Self {
+		let angle = panning.clamp(0.0, 1.0) * std::f64::consts::FRAC_PI_2;
+		Self::new(
+			self.left * angle.cos() as f32,
+			self.right * angle.sin() as f32,
+		)
+	}
+}
+
+impl Add for Frame {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self {
+		Self::new(self.left + rhs.left, self.right + rhs.right)
+	}
+}
+
+impl Sub for Frame {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self {
+		Self::new(self.left - rhs.left, self.right - rhs.right)
+	}
+}
+
+impl Mul<f64> for Frame {
+	type Output = Self;
+
+	fn mul(self, rhs: f64) -> Self {
+		Self::new(self.left * rhs as f32, self.right * rhs as f32)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn panning_center_scales_both_channels_to_equal_power() {
+		let panned = Frame::from_mono(1.0).panned(0.5);
+		assert!((panned.left - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+		assert!((panned.right - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+	}
+
+	#[test]
+	fn panning_hard_left_silences_the_right_channel() {
+		let panned = Frame::from_mono(1.0).panned(0.0);
+		assert!((panned.left - 1.0).abs() < 1e-6);
+		assert!(panned.right.abs() < 1e-6);
+	}
+
+	#[test]
+	fn panning_hard_right_silences_the_left_channel() {
+		let panned = Frame::from_mono(1.0).panned(1.0);
+		assert!(panned.left.abs() < 1e-6);
+		assert!((panned.right - 1.0).abs() < 1e-6);
+	}
+}
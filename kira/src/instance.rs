@@ -0,0 +1,64 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{
+	frame::Frame,
+	track::{TrackId, Tracks, MAIN_TRACK_ID},
+};
+
+static NEXT_INSTANCE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A unique identifier for an instance of a playing sound.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct InstanceId(usize);
+
+impl InstanceId {
+	pub(crate) fn new() -> Self {
+		Self(NEXT_INSTANCE_ID.fetch_add(1, Ordering::Relaxed))
+	}
+}
+
+/// Settings for an individual instance of a sound.
+#[derive(Debug, Copy, Clone)]
+pub struct InstanceSettings {
+	pub volume: f64,
+	pub pitch: f64,
+	pub panning: f64,
+	pub position: f64,
+	/// Which track this instance's output is mixed into.
+	pub track: TrackId,
+}
+
+impl Default for InstanceSettings {
+	fn default() -> Self {
+		Self {
+			volume: 1.0,
+			pitch: 1.0,
+			panning: 0.5,
+			position: 0.0,
+			track: MAIN_TRACK_ID,
+		}
+	}
+}
+
+/// The audio-thread side of a playing instance.
+///
+/// Owns the settings it was started with and knows how to route its
+/// rendered output into the track graph; decoding samples out of the
+/// underlying sound is handled elsewhere.
+pub(crate) struct Instance {
+	settings: InstanceSettings,
+}
+
+impl Instance {
+	pub fn new(settings: InstanceSettings) -> Self {
+		Self { settings }
+	}
+
+	/// Applies this instance's volume and panning to a frame it rendered
+	/// for the current sample, then mixes it into the track it's routed
+	/// to via [`InstanceSettings::track`].
+	pub fn mix_into(&self, frame: Frame, tracks: &mut Tracks) {
+		let frame = (frame * self.settings.volume).panned(self.settings.panning);
+		tracks.add_input(self.settings.track, frame);
+	}
+}
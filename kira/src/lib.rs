@@ -0,0 +1,7 @@
+pub mod effect;
+pub mod frame;
+pub mod instance;
+pub mod manager;
+pub mod track;
+
+pub use manager::{AudioManager, AudioManagerSettings};
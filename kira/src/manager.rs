@@ -1,11 +1,16 @@
 mod backend;
-mod command;
+pub(crate) mod command;
 pub mod error;
 mod resources;
 
+use std::sync::{
+	atomic::{AtomicBool, Ordering},
+	Arc, Mutex,
+};
+
 use cpal::{
 	traits::{DeviceTrait, HostTrait, StreamTrait},
-	Stream,
+	Device, Stream,
 };
 use ringbuf::{Producer, RingBuffer};
 
@@ -18,13 +23,64 @@ use self::{
 		UnusedResourceConsumers,
 	},
 };
+use crate::track::{Track, TrackHandle, TrackId, TrackSettings};
 
 pub struct AudioManagerSettings {
 	pub sound_capacity: usize,
 	pub command_capacity: usize,
 }
 
+/// Whether the output device the [`AudioManager`] is currently playing
+/// through is connected.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DeviceState {
+	Connected,
+	/// The device disappeared (e.g. it was unplugged) and the manager is
+	/// playing silently until a new default device is found.
+	Disconnected,
+}
+
+fn build_stream(
+	device: &Device,
+	backend: Arc<Mutex<Backend>>,
+	device_lost: Arc<AtomicBool>,
+) -> Result<Stream, SetupError> {
+	let config = device.default_output_config()?.config();
+	let channels = config.channels;
+	backend
+		.lock()
+		.expect("backend mutex is poisoned")
+		.set_sample_rate(config.sample_rate.0);
+	let stream = device.build_output_stream(
+		&config,
+		move |data: &mut [f32], _| {
+			let mut backend = backend.lock().expect("backend mutex is poisoned");
+			for frame in data.chunks_exact_mut(channels as usize) {
+				let out = backend.process();
+				if channels == 1 {
+					frame[0] = (out.left + out.right) / 2.0;
+				} else {
+					frame[0] = out.left;
+					frame[1] = out.right;
+				}
+			}
+		},
+		move |_| {
+			// the stream's error callback runs on the audio thread, which
+			// can't safely tear down and rebuild the stream itself, so it
+			// just raises a flag for the manager to notice and act on
+			device_lost.store(true, Ordering::SeqCst);
+		},
+	)?;
+	stream.play()?;
+	Ok(stream)
+}
+
 pub struct AudioManager {
+	host: cpal::Host,
+	backend: Arc<Mutex<Backend>>,
+	device_lost: Arc<AtomicBool>,
+	device_state: DeviceState,
 	command_producer: Producer<Command>,
 	resource_controllers: ResourceControllers,
 	unused_resource_consumers: UnusedResourceConsumers,
@@ -39,35 +95,76 @@ impl AudioManager {
 			.ok_or(SetupError::NoDefaultOutputDevice)?;
 		let config = device.default_output_config()?.config();
 		let sample_rate = config.sample_rate;
-		let channels = config.channels;
 		let (unused_resource_producers, unused_resource_consumers) =
 			create_unused_resource_channels(&settings);
 		let (resources, resource_controllers) =
 			create_resources(&settings, unused_resource_producers);
 		let (command_producer, command_consumer) =
 			RingBuffer::new(settings.command_capacity).split();
-		let mut backend = Backend::new(sample_rate.0, resources, command_consumer);
-		let stream = device.build_output_stream(
-			&config,
-			move |data: &mut [f32], _| {
-				for frame in data.chunks_exact_mut(channels as usize) {
-					let out = backend.process();
-					if channels == 1 {
-						frame[0] = (out.left + out.right) / 2.0;
-					} else {
-						frame[0] = out.left;
-						frame[1] = out.right;
-					}
-				}
-			},
-			move |_| {},
-		)?;
-		stream.play()?;
+		let backend = Arc::new(Mutex::new(Backend::new(
+			sample_rate.0,
+			resources,
+			command_consumer,
+		)));
+		let device_lost = Arc::new(AtomicBool::new(false));
+		let stream = build_stream(&device, backend.clone(), device_lost.clone())?;
 		Ok(Self {
+			host,
+			backend,
+			device_lost,
+			device_state: DeviceState::Connected,
 			command_producer,
 			resource_controllers,
 			unused_resource_consumers,
 			_stream: stream,
 		})
 	}
+
+	/// Returns whether the output device is currently connected.
+	pub fn device_state(&self) -> DeviceState {
+		self.device_state
+	}
+
+	/// Checks whether the output stream reported a device loss since the
+	/// last call, and if so, tears it down and rebuilds it against
+	/// whichever device is the default now.
+	///
+	/// The host application should call this periodically (e.g. once per
+	/// frame); loaded sounds and in-flight sequences are preserved across
+	/// the rebuild since they live in the [`Backend`], which is kept
+	/// alive independently of any one [`Stream`].
+	///
+	/// A rebuild attempt can itself fail (e.g. no default device is
+	/// available yet), so this keeps retrying on every call while
+	/// `device_state` is still [`DeviceState::Disconnected`], not just on
+	/// the call where `device_lost` first flips — otherwise a failed
+	/// rebuild would consume the one-shot flag and leave the manager
+	/// silently stuck disconnected forever.
+	pub fn check_for_device_changes(&mut self) -> Result<(), SetupError> {
+		if !self.device_lost.swap(false, Ordering::SeqCst)
+			&& self.device_state == DeviceState::Connected
+		{
+			return Ok(());
+		}
+		self.device_state = DeviceState::Disconnected;
+		let device = self
+			.host
+			.default_output_device()
+			.ok_or(SetupError::NoDefaultOutputDevice)?;
+		self._stream = build_stream(&device, self.backend.clone(), self.device_lost.clone())?;
+		self.device_state = DeviceState::Connected;
+		Ok(())
+	}
+
+	/// Creates a sub-track that instances (or other tracks) can be routed
+	/// through, giving them their own volume, panning, and effect chain
+	/// before being mixed into the track's parent.
+	pub fn add_sub_track(&mut self, settings: TrackSettings) -> Result<TrackHandle, SetupError> {
+		let id = TrackId::new();
+		let (track, command_producer) = Track::new(settings);
+		self.command_producer
+			.push(Command::AddSubTrack(id, track))
+			.map_err(|_| SetupError::CommandQueueFull)?;
+		Ok(TrackHandle::new(id, command_producer))
+	}
 }
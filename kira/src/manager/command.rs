@@ -0,0 +1,5 @@
+use crate::track::{Track, TrackId};
+
+pub(crate) enum Command {
+	AddSubTrack(TrackId, Track),
+}
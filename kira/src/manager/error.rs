@@ -0,0 +1,52 @@
+use std::fmt::{self, Display};
+
+use cpal::{BuildStreamError, DefaultStreamConfigError, PlayStreamError};
+
+/// Something that went wrong when setting up an [`AudioManager`](super::AudioManager).
+#[derive(Debug)]
+pub enum SetupError {
+	/// No default audio output device was found.
+	NoDefaultOutputDevice,
+	/// Could not get the default configuration for the output device.
+	DefaultStreamConfigError(DefaultStreamConfigError),
+	/// Could not build the output stream.
+	BuildStreamError(BuildStreamError),
+	/// Could not start the output stream.
+	PlayStreamError(PlayStreamError),
+	/// A command could not be sent because the command queue is full.
+	CommandQueueFull,
+}
+
+impl Display for SetupError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::NoDefaultOutputDevice => f.write_str("no default output device was found"),
+			Self::DefaultStreamConfigError(error) => error.fmt(f),
+			Self::BuildStreamError(error) => error.fmt(f),
+			Self::PlayStreamError(error) => error.fmt(f),
+			Self::CommandQueueFull => {
+				f.write_str("could not add the command to the queue because it is full")
+			}
+		}
+	}
+}
+
+impl std::error::Error for SetupError {}
+
+impl From<DefaultStreamConfigError> for SetupError {
+	fn from(error: DefaultStreamConfigError) -> Self {
+		Self::DefaultStreamConfigError(error)
+	}
+}
+
+impl From<BuildStreamError> for SetupError {
+	fn from(error: BuildStreamError) -> Self {
+		Self::BuildStreamError(error)
+	}
+}
+
+impl From<PlayStreamError> for SetupError {
+	fn from(error: PlayStreamError) -> Self {
+		Self::PlayStreamError(error)
+	}
+}
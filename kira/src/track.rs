@@ -0,0 +1,234 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use indexmap::IndexMap;
+use ringbuf::{Consumer, Producer, RingBuffer};
+
+use crate::{effect::Effect, frame::Frame, manager::command::Command};
+
+static NEXT_TRACK_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A unique identifier for a [`Track`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TrackId(usize);
+
+impl TrackId {
+	pub(crate) fn new() -> Self {
+		Self(NEXT_TRACK_ID.fetch_add(1, Ordering::Relaxed))
+	}
+}
+
+/// The main track that the final mix is routed through.
+pub const MAIN_TRACK_ID: TrackId = TrackId(usize::MAX);
+
+/// Settings for an individual [`Track`].
+pub struct TrackSettings {
+	pub volume: f64,
+	pub panning: f64,
+	pub parent: TrackId,
+	pub command_capacity: usize,
+}
+
+impl TrackSettings {
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+impl Default for TrackSettings {
+	fn default() -> Self {
+		Self {
+			volume: 1.0,
+			panning: 0.5,
+			parent: MAIN_TRACK_ID,
+			command_capacity: 16,
+		}
+	}
+}
+
+pub(crate) enum TrackCommand {
+	SetVolume(f64),
+	SetPanning(f64),
+}
+
+/// A sub-bus that instances (and other tracks) can be routed through.
+///
+/// Each block, the backend sums the output of every instance and track
+/// routed to this one, runs the result through this track's effect chain,
+/// and applies its volume and panning before handing the frame to its
+/// parent track.
+pub(crate) struct Track {
+	volume: f64,
+	panning: f64,
+	parent: TrackId,
+	effects: Vec<Box<dyn Effect>>,
+	command_consumer: Consumer<TrackCommand>,
+	input: Frame,
+}
+
+impl Track {
+	pub fn new(settings: TrackSettings) -> (Self, Producer<TrackCommand>) {
+		let (command_producer, command_consumer) =
+			RingBuffer::new(settings.command_capacity).split();
+		(
+			Self {
+				volume: settings.volume,
+				panning: settings.panning,
+				parent: settings.parent,
+				effects: vec![],
+				command_consumer,
+				input: Frame::from_mono(0.0),
+			},
+			command_producer,
+		)
+	}
+
+	pub fn parent(&self) -> TrackId {
+		self.parent
+	}
+
+	pub fn add_effect(&mut self, effect: impl Effect + 'static) {
+		self.effects.push(Box::new(effect));
+	}
+
+	/// Mixes a frame produced by an instance or a child track into this
+	/// track's input for the current sample.
+	pub fn add_input(&mut self, frame: Frame) {
+		self.input = self.input + frame;
+	}
+
+	fn run_commands(&mut self) {
+		while let Some(command) = self.command_consumer.pop() {
+			match command {
+				TrackCommand::SetVolume(volume) => self.volume = volume,
+				TrackCommand::SetPanning(panning) => self.panning = panning,
+			}
+		}
+	}
+
+	/// Runs the effect chain over the accumulated input, applies volume
+	/// and panning, and returns the frame to be mixed into the parent
+	/// track. Resets the input accumulator for the next sample.
+	pub fn process(&mut self, dt: f64) -> Frame {
+		self.run_commands();
+		let mut frame = self.input;
+		self.input = Frame::from_mono(0.0);
+		for effect in self.effects.iter_mut() {
+			frame = effect.process(frame, dt);
+		}
+		(frame * self.volume).panned(self.panning)
+	}
+}
+
+/// Controls a track's volume and panning from outside the audio thread.
+pub struct TrackHandle {
+	id: TrackId,
+	command_producer: Producer<TrackCommand>,
+}
+
+impl TrackHandle {
+	pub(crate) fn new(id: TrackId, command_producer: Producer<TrackCommand>) -> Self {
+		Self {
+			id,
+			command_producer,
+		}
+	}
+
+	pub fn id(&self) -> TrackId {
+		self.id
+	}
+
+	pub fn set_volume(&mut self, volume: f64) {
+		self.command_producer
+			.push(TrackCommand::SetVolume(volume))
+			.ok();
+	}
+
+	pub fn set_panning(&mut self, panning: f64) {
+		self.command_producer
+			.push(TrackCommand::SetPanning(panning))
+			.ok();
+	}
+}
+
+/// Owns every [`Track`] created through
+/// [`AudioManager::add_sub_track`](crate::manager::AudioManager::add_sub_track)
+/// along with the implicit main track, and mixes them down to a single
+/// frame each sample.
+pub(crate) struct Tracks {
+	main: Track,
+	sub_tracks: IndexMap<TrackId, Track>,
+}
+
+impl Tracks {
+	pub fn new() -> Self {
+		let (main, _) = Track::new(TrackSettings {
+			parent: MAIN_TRACK_ID,
+			..TrackSettings::default()
+		});
+		Self {
+			main,
+			sub_tracks: IndexMap::new(),
+		}
+	}
+
+	pub fn run_command(&mut self, command: Command) {
+		match command {
+			Command::AddSubTrack(id, track) => {
+				self.sub_tracks.insert(id, track);
+			}
+		}
+	}
+
+	/// Mixes a frame produced by an instance (or another track) into the
+	/// track it's routed to.
+	pub fn add_input(&mut self, track: TrackId, frame: Frame) {
+		if track == MAIN_TRACK_ID {
+			self.main.add_input(frame);
+		} else if let Some(track) = self.sub_tracks.get_mut(&track) {
+			track.add_input(frame);
+		}
+	}
+
+	/// Processes every sub-track and routes its output into its parent,
+	/// then processes the main track and returns the final mixed frame.
+	///
+	/// A track can only be routed to a parent that already exists, so
+	/// [`AudioManager::add_sub_track`](crate::manager::AudioManager::add_sub_track)
+	/// always inserts parents before the children routed to them —
+	/// processing sub-tracks in *reverse* insertion order therefore always
+	/// reaches a child before its parent, so the child's output lands in
+	/// the parent's accumulator in time to be included in this same call,
+	/// instead of being delayed until the next one.
+	pub fn process(&mut self, dt: f64) -> Frame {
+		for id in self.sub_tracks.keys().copied().collect::<Vec<_>>().into_iter().rev() {
+			let frame = self.sub_tracks.get_mut(&id).unwrap().process(dt);
+			let parent = self.sub_tracks.get(&id).unwrap().parent();
+			self.add_input(parent, frame);
+		}
+		self.main.process(dt)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn a_sub_track_nested_under_another_sub_track_reaches_main_in_one_process_call() {
+		let mut tracks = Tracks::new();
+		let parent_id = TrackId::new();
+		let (parent, _) = Track::new(TrackSettings::default());
+		tracks.run_command(Command::AddSubTrack(parent_id, parent));
+
+		let child_id = TrackId::new();
+		let (child, _) = Track::new(TrackSettings {
+			parent: parent_id,
+			..TrackSettings::default()
+		});
+		tracks.run_command(Command::AddSubTrack(child_id, child));
+
+		tracks.add_input(child_id, Frame::from_mono(1.0));
+		let output = tracks.process(1.0 / 44100.0);
+		assert!(output.left.abs() > 0.0);
+	}
+}